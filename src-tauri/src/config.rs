@@ -0,0 +1,85 @@
+// src-tauri/src/config.rs
+//!
+//! Centralized runtime configuration: ffmpeg/ffprobe binary locations,
+//! thumbnail/preview dimensions, capture timestamps, default output formats,
+//! and the scratch-file directory. Loaded once at startup from
+//! `CLIPJOURNEY_`-prefixed environment variables (falling back to the
+//! project's existing defaults) and managed as Tauri state, so commands read
+//! from it instead of hardcoding literals.
+
+use std::env;
+
+use crate::media::{Backends, PreviewFormat, ThumbnailFormat};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Size {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub backends: Backends,
+    pub thumbnail_size: Size,
+    pub timeline_thumbnail_size: Size,
+    pub preview_size: Size,
+    pub thumbnail_timestamp_secs: f64,
+    pub preview_duration_secs: f64,
+    pub default_thumbnail_format: ThumbnailFormat,
+    pub default_preview_format: PreviewFormat,
+}
+
+impl Config {
+    /// Loads configuration from `CLIPJOURNEY_*` environment variables,
+    /// falling back to the project's existing defaults when a variable is
+    /// unset or fails to parse.
+    pub fn load() -> Self {
+        Config {
+            backends: Backends {
+                ffmpeg_path: env_string("CLIPJOURNEY_FFMPEG_PATH", "ffmpeg"),
+                ffprobe_path: env_string("CLIPJOURNEY_FFPROBE_PATH", "ffprobe"),
+                temp_dir: env::var("CLIPJOURNEY_TEMP_DIR")
+                    .map(Into::into)
+                    .unwrap_or_else(|_| std::env::temp_dir()),
+            },
+            thumbnail_size: Size {
+                width: env_u32("CLIPJOURNEY_THUMBNAIL_WIDTH", 160),
+                height: env_u32("CLIPJOURNEY_THUMBNAIL_HEIGHT", 90),
+            },
+            timeline_thumbnail_size: Size {
+                width: env_u32("CLIPJOURNEY_TIMELINE_THUMBNAIL_WIDTH", 80),
+                height: env_u32("CLIPJOURNEY_TIMELINE_THUMBNAIL_HEIGHT", 45),
+            },
+            preview_size: Size {
+                width: env_u32("CLIPJOURNEY_PREVIEW_WIDTH", 160),
+                height: env_u32("CLIPJOURNEY_PREVIEW_HEIGHT", 90),
+            },
+            thumbnail_timestamp_secs: env_f64("CLIPJOURNEY_THUMBNAIL_TIMESTAMP_SECS", 1.0),
+            preview_duration_secs: env_f64("CLIPJOURNEY_PREVIEW_DURATION_SECS", 2.0),
+            default_thumbnail_format: ThumbnailFormat::parse(
+                env::var("CLIPJOURNEY_THUMBNAIL_FORMAT").ok().as_deref(),
+            ),
+            default_preview_format: PreviewFormat::parse(
+                env::var("CLIPJOURNEY_PREVIEW_FORMAT").ok().as_deref(),
+            ),
+        }
+    }
+}
+
+fn env_string(key: &str, default: &str) -> String {
+    env::var(key).unwrap_or_else(|_| default.to_string())
+}
+
+fn env_u32(key: &str, default: u32) -> u32 {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+fn env_f64(key: &str, default: f64) -> f64 {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
@@ -0,0 +1,147 @@
+// src-tauri/src/exclude.rs
+//!
+//! Non-destructive "exclude" workflow. Moving a file into a sibling
+//! `Excluded/` folder is recorded in a persisted move journal (backed by
+//! `tauri_plugin_store`) so it can be undone with [`unexclude_file`], falls
+//! back to copy-then-delete when the move crosses a filesystem boundary, and
+//! suffixes the destination name on collision instead of overwriting.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+const JOURNAL_STORE: &str = "exclude_journal.json";
+const JOURNAL_KEY: &str = "moves";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MoveRecord {
+    original_path: String,
+    excluded_path: String,
+    moved_at_secs: u64,
+}
+
+fn load_journal(app: &AppHandle) -> Result<Vec<MoveRecord>, String> {
+    let store = app
+        .store(JOURNAL_STORE)
+        .map_err(|e| format!("Failed to open move journal: {}", e))?;
+    Ok(store
+        .get(JOURNAL_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn save_journal(app: &AppHandle, records: &[MoveRecord]) -> Result<(), String> {
+    let store = app
+        .store(JOURNAL_STORE)
+        .map_err(|e| format!("Failed to open move journal: {}", e))?;
+    store.set(JOURNAL_KEY, json!(records));
+    store
+        .save()
+        .map_err(|e| format!("Failed to persist move journal: {}", e))
+}
+
+/// Appends a suffix like ` (2)`, ` (3)`, ... to `path`'s file stem until the
+/// result doesn't already exist.
+fn unique_destination(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+
+    let parent = path.parent().unwrap_or_else(|| Path::new(""));
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
+    let ext = path.extension().and_then(|s| s.to_str());
+
+    let mut n = 2;
+    loop {
+        let candidate_name = match ext {
+            Some(ext) => format!("{} ({}).{}", stem, n, ext),
+            None => format!("{} ({})", stem, n),
+        };
+        let candidate = parent.join(candidate_name);
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// `EXDEV` on Unix, `ERROR_NOT_SAME_DEVICE` on Windows: `fs::rename` can't
+/// move across filesystem boundaries. The raw codes collide between
+/// platforms (17 is `EEXIST` on Unix but `ERROR_NOT_SAME_DEVICE` on Windows),
+/// so each must only be checked on its own OS.
+#[cfg(unix)]
+fn is_cross_device_error(err: &std::io::Error) -> bool {
+    err.raw_os_error() == Some(18)
+}
+
+#[cfg(windows)]
+fn is_cross_device_error(err: &std::io::Error) -> bool {
+    err.raw_os_error() == Some(17)
+}
+
+/// Moves `src` to `dst`, falling back to copy-then-delete when the paths are
+/// on different filesystems.
+fn move_file(src: &Path, dst: &Path) -> Result<(), String> {
+    match fs::rename(src, dst) {
+        Ok(()) => Ok(()),
+        Err(e) if is_cross_device_error(&e) => {
+            fs::copy(src, dst).map_err(|e| format!("Failed to copy file: {}", e))?;
+            fs::remove_file(src)
+                .map_err(|e| format!("Failed to remove original after copy: {}", e))
+        }
+        Err(e) => Err(format!("Failed to move file: {}", e)),
+    }
+}
+
+pub fn exclude_file(file_path: String, app: &AppHandle) -> Result<String, String> {
+    let path = Path::new(&file_path);
+    let parent = path.parent().ok_or("No parent directory")?;
+    let filename = path.file_name().ok_or("No filename")?;
+
+    let excluded_folder = parent.join("Excluded");
+    fs::create_dir_all(&excluded_folder)
+        .map_err(|e| format!("Failed to create Excluded folder: {}", e))?;
+
+    let dest = unique_destination(excluded_folder.join(filename));
+    move_file(path, &dest)?;
+
+    let mut journal = load_journal(app)?;
+    journal.push(MoveRecord {
+        original_path: file_path,
+        excluded_path: dest.to_string_lossy().into_owned(),
+        moved_at_secs: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+    });
+    save_journal(app, &journal)?;
+
+    dest.to_str()
+        .ok_or_else(|| "Invalid path".to_string())
+        .map(str::to_string)
+}
+
+/// Restores a file previously moved by [`exclude_file`] back to its original
+/// location (suffixing the name on collision), using the move journal to
+/// look up where it came from.
+pub fn unexclude_file(excluded_path: String, app: &AppHandle) -> Result<String, String> {
+    let mut journal = load_journal(app)?;
+    let idx = journal
+        .iter()
+        .position(|record| record.excluded_path == excluded_path)
+        .ok_or_else(|| "No record of this file being excluded".to_string())?;
+    let record = journal.remove(idx);
+
+    let dest = unique_destination(PathBuf::from(&record.original_path));
+    move_file(Path::new(&record.excluded_path), &dest)?;
+
+    save_journal(app, &journal)?;
+
+    dest.to_str()
+        .ok_or_else(|| "Invalid path".to_string())
+        .map(str::to_string)
+}
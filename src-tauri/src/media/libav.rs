@@ -0,0 +1,228 @@
+// src-tauri/src/media/libav.rs
+//!
+//! In-process media backend built on `ffmpeg-next`. Opens the input once,
+//! reads the format context for duration, seeks, decodes a frame, and scales
+//! it via `swscale` -- avoiding the external `ffmpeg`/`ffprobe` binaries and
+//! the per-call process-spawn cost of [`super::subprocess`].
+//!
+//! Enabled with the `libav` Cargo feature.
+
+use ffmpeg_next as ffmpeg;
+use ffmpeg_next::format::Pixel;
+use ffmpeg_next::software::scaling::{context::Context as ScalingContext, flag::Flags};
+use ffmpeg_next::util::frame::video::Video;
+
+use super::{MediaError, ThumbnailFormat};
+
+fn decode_err(e: ffmpeg::Error) -> MediaError {
+    MediaError::Decode(e.to_string())
+}
+
+pub fn get_duration(path: &str) -> Result<f64, MediaError> {
+    ffmpeg::init().map_err(decode_err)?;
+    let ictx = ffmpeg::format::input(&path).map_err(decode_err)?;
+    let duration = ictx.duration() as f64 / f64::from(ffmpeg::ffi::AV_TIME_BASE);
+    Ok(duration)
+}
+
+/// Decodes the first video frame at or after `timestamp_secs`, scales it to
+/// `width x height`, and encodes the result in `format`.
+pub fn decode_frame(
+    path: &str,
+    timestamp_secs: f64,
+    width: u32,
+    height: u32,
+    format: ThumbnailFormat,
+) -> Result<Vec<u8>, MediaError> {
+    ffmpeg::init().map_err(decode_err)?;
+    let mut ictx = ffmpeg::format::input(&path).map_err(decode_err)?;
+
+    let stream = ictx
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or_else(|| MediaError::Decode("no video stream found".into()))?;
+    let stream_index = stream.index();
+    // Copy the `Rational` time_base out of `stream` now: `stream` borrows
+    // `ictx` immutably, but `ictx.seek` below needs `&mut ictx`, and this
+    // scope has no other use for the borrow once `context_decoder` is built.
+    let time_base = stream.time_base();
+
+    let context_decoder =
+        ffmpeg::codec::context::Context::from_parameters(stream.parameters()).map_err(decode_err)?;
+    let mut decoder = context_decoder.decoder().video().map_err(decode_err)?;
+
+    // `Input::seek` takes AV_TIME_BASE units regardless of which stream ends
+    // up selected (it calls `avformat_seek_file` with stream_index = -1), not
+    // the target stream's own time_base.
+    let seek_target = (timestamp_secs * f64::from(ffmpeg::ffi::AV_TIME_BASE)) as i64;
+    ictx.seek(seek_target, ..seek_target).map_err(decode_err)?;
+    decoder.flush();
+
+    let mut scaler = ScalingContext::get(
+        decoder.format(),
+        decoder.width(),
+        decoder.height(),
+        Pixel::RGB24,
+        width,
+        height,
+        Flags::BILINEAR,
+    )
+    .map_err(decode_err)?;
+
+    // A keyframe seek can land before the requested timestamp; decode
+    // forward until we reach a frame at or after it.
+    let pts_target = (timestamp_secs / f64::from(time_base)) as i64;
+    let mut decoded = Video::empty();
+    for (stream, packet) in ictx.packets() {
+        if stream.index() != stream_index {
+            continue;
+        }
+        decoder.send_packet(&packet).map_err(decode_err)?;
+        if decoder.receive_frame(&mut decoded).is_ok() {
+            if decoded.pts().unwrap_or(0) < pts_target {
+                continue;
+            }
+            let mut scaled = Video::empty();
+            scaler.run(&decoded, &mut scaled).map_err(decode_err)?;
+            return encode_image(&scaled, width, height, format);
+        }
+    }
+
+    Err(MediaError::Decode(format!(
+        "no frame found at or after {:.3}s",
+        timestamp_secs
+    )))
+}
+
+/// Extracts `count` evenly spaced frames from a single opened input, seeking
+/// to each position in turn, scaling each to `width x height` and encoding
+/// in `format`.
+pub fn generate_timeline_frames(
+    path: &str,
+    count: u32,
+    width: u32,
+    height: u32,
+    format: ThumbnailFormat,
+) -> Result<Vec<Vec<u8>>, MediaError> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    ffmpeg::init().map_err(decode_err)?;
+    let mut ictx = ffmpeg::format::input(&path).map_err(decode_err)?;
+    let duration = ictx.duration() as f64 / f64::from(ffmpeg::ffi::AV_TIME_BASE);
+    if duration <= 0.0 {
+        return Err(MediaError::Decode("video reports zero or negative duration".into()));
+    }
+    let interval = duration / count as f64;
+
+    let stream = ictx
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or_else(|| MediaError::Decode("no video stream found".into()))?;
+    let stream_index = stream.index();
+    // Copy the `Rational` time_base out of `stream` now: each loop iteration
+    // below calls `ictx.seek`, which needs `&mut ictx` and would otherwise
+    // conflict with `stream`'s immutable borrow of `ictx`.
+    let time_base = stream.time_base();
+
+    let context_decoder =
+        ffmpeg::codec::context::Context::from_parameters(stream.parameters()).map_err(decode_err)?;
+    let mut decoder = context_decoder.decoder().video().map_err(decode_err)?;
+
+    let mut scaler = ScalingContext::get(
+        decoder.format(),
+        decoder.width(),
+        decoder.height(),
+        Pixel::RGB24,
+        width,
+        height,
+        Flags::BILINEAR,
+    )
+    .map_err(decode_err)?;
+
+    let mut frames = Vec::with_capacity(count as usize);
+    let mut decoded = Video::empty();
+
+    for i in 0..count {
+        let timestamp_secs = i as f64 * interval;
+        let seek_target = (timestamp_secs * f64::from(ffmpeg::ffi::AV_TIME_BASE)) as i64;
+        ictx.seek(seek_target, ..seek_target).map_err(decode_err)?;
+        decoder.flush();
+
+        // A keyframe seek can land before the requested timestamp; decode
+        // forward until we reach a frame at or after it.
+        let pts_target = (timestamp_secs / f64::from(time_base)) as i64;
+        let mut found = false;
+        for (s, packet) in ictx.packets() {
+            if s.index() != stream_index {
+                continue;
+            }
+            decoder.send_packet(&packet).map_err(decode_err)?;
+            if decoder.receive_frame(&mut decoded).is_ok() {
+                if decoded.pts().unwrap_or(0) < pts_target {
+                    continue;
+                }
+                let mut scaled = Video::empty();
+                scaler.run(&decoded, &mut scaled).map_err(decode_err)?;
+                frames.push(encode_image(&scaled, width, height, format)?);
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            break;
+        }
+    }
+
+    // Seeking can land a frame short near the tail of the stream; pad with
+    // the last good frame so callers get exactly `count` entries. If no
+    // frame was decoded at all, the result is empty.
+    if let Some(last) = frames.last().cloned() {
+        while frames.len() < count as usize {
+            frames.push(last.clone());
+        }
+    }
+
+    Ok(frames)
+}
+
+fn encode_image(
+    frame: &Video,
+    width: u32,
+    height: u32,
+    format: ThumbnailFormat,
+) -> Result<Vec<u8>, MediaError> {
+    let mut buf = Vec::new();
+    let data = packed_rgb(frame, width, height);
+
+    let result = match format {
+        ThumbnailFormat::Jpeg => image::codecs::jpeg::JpegEncoder::new(&mut buf)
+            .encode(&data, width, height, image::ColorType::Rgb8),
+        ThumbnailFormat::Png => image::codecs::png::PngEncoder::new(&mut buf)
+            .encode(&data, width, height, image::ColorType::Rgb8),
+        ThumbnailFormat::WebP => image::codecs::webp::WebPEncoder::new_lossless(&mut buf)
+            .encode(&data, width, height, image::ColorType::Rgb8),
+    };
+    result.map_err(|e| MediaError::Decode(format!("{:?} encode: {}", format, e)))?;
+
+    Ok(buf)
+}
+
+/// Copies `frame`'s RGB24 plane into a tightly packed `width * height * 3`
+/// buffer. swscale pads each row to its own stride (`frame.stride(0)`), which
+/// is only equal to `width * 3` when that happens to already be aligned; the
+/// `image` encoders expect no padding, so rows must be copied one at a time.
+fn packed_rgb(frame: &Video, width: u32, height: u32) -> Vec<u8> {
+    let stride = frame.stride(0);
+    let row_len = width as usize * 3;
+    let data = frame.data(0);
+
+    let mut packed = Vec::with_capacity(row_len * height as usize);
+    for row in 0..height as usize {
+        let start = row * stride;
+        packed.extend_from_slice(&data[start..start + row_len]);
+    }
+    packed
+}
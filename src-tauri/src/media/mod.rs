@@ -0,0 +1,233 @@
+// src-tauri/src/media/mod.rs
+//!
+//! Backend-agnostic media probing/decoding used by the Tauri commands in
+//! `lib.rs`. Two backends exist:
+//!
+//! - [`subprocess`]: shells out to the system `ffmpeg`/`ffprobe` binaries.
+//!   This is the default and requires no additional build dependencies.
+//! - [`libav`]: decodes in-process via the `ffmpeg-next` crate. Enabled with
+//!   the `libav` Cargo feature; falls back to `subprocess` when disabled.
+
+mod subprocess;
+
+#[cfg(feature = "libav")]
+mod libav;
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// Error produced while probing or decoding a video, independent of which
+/// backend produced it.
+#[derive(Debug)]
+pub enum MediaError {
+    /// The backend could not be started (e.g. `ffmpeg`/`ffprobe` missing from PATH).
+    Spawn(String),
+    /// The backend ran but reported a decode/seek/scale failure.
+    Decode(String),
+    /// Backend output could not be parsed into the expected shape.
+    Parse(String),
+    /// An I/O error occurred while reading/writing temp files.
+    Io(String),
+}
+
+impl fmt::Display for MediaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MediaError::Spawn(msg) => write!(f, "failed to start media backend: {}", msg),
+            MediaError::Decode(msg) => write!(f, "decode error: {}", msg),
+            MediaError::Parse(msg) => write!(f, "failed to parse backend output: {}", msg),
+            MediaError::Io(msg) => write!(f, "I/O error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MediaError {}
+
+impl From<MediaError> for String {
+    fn from(err: MediaError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Output format for a generated thumbnail or timeline frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailFormat {
+    Jpeg,
+    Png,
+    WebP,
+}
+
+impl ThumbnailFormat {
+    /// Parses a command argument (e.g. `"webp"`), defaulting to `Jpeg` when
+    /// absent or unrecognized.
+    pub fn parse(value: Option<&str>) -> Self {
+        match value.map(str::to_lowercase).as_deref() {
+            Some("png") => ThumbnailFormat::Png,
+            Some("webp") => ThumbnailFormat::WebP,
+            _ => ThumbnailFormat::Jpeg,
+        }
+    }
+
+    /// ffmpeg video codec used to encode this format.
+    pub fn codec(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "mjpeg",
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::WebP => "libwebp",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "jpg",
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::WebP => "webp",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "image/jpeg",
+            ThumbnailFormat::Png => "image/png",
+            ThumbnailFormat::WebP => "image/webp",
+        }
+    }
+}
+
+impl Default for ThumbnailFormat {
+    fn default() -> Self {
+        ThumbnailFormat::Jpeg
+    }
+}
+
+/// Output format for a generated hover-preview clip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewFormat {
+    /// Animated, looping WebP.
+    WebP,
+    /// H.264 MP4 with `+faststart` (muted); not fragmented.
+    Mp4,
+}
+
+impl PreviewFormat {
+    /// Parses a command argument (e.g. `"mp4"`), defaulting to `WebP` when
+    /// absent or unrecognized.
+    pub fn parse(value: Option<&str>) -> Self {
+        match value.map(str::to_lowercase).as_deref() {
+            Some("mp4") => PreviewFormat::Mp4,
+            _ => PreviewFormat::WebP,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            PreviewFormat::WebP => "webp",
+            PreviewFormat::Mp4 => "mp4",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            PreviewFormat::WebP => "image/webp",
+            PreviewFormat::Mp4 => "video/mp4",
+        }
+    }
+}
+
+impl Default for PreviewFormat {
+    fn default() -> Self {
+        PreviewFormat::WebP
+    }
+}
+
+/// ffmpeg/ffprobe binary locations and scratch-file directory used by the
+/// `subprocess` backend. Ignored by `libav`, which never shells out.
+#[derive(Debug, Clone)]
+pub struct Backends {
+    pub ffmpeg_path: String,
+    pub ffprobe_path: String,
+    pub temp_dir: PathBuf,
+}
+
+impl Default for Backends {
+    fn default() -> Self {
+        Backends {
+            ffmpeg_path: "ffmpeg".to_string(),
+            ffprobe_path: "ffprobe".to_string(),
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+}
+
+/// Returns the duration of `path` in seconds.
+pub fn get_duration(path: &str, backends: &Backends) -> Result<f64, MediaError> {
+    #[cfg(feature = "libav")]
+    {
+        let _ = backends;
+        libav::get_duration(path)
+    }
+    #[cfg(not(feature = "libav"))]
+    {
+        subprocess::get_duration(path, backends)
+    }
+}
+
+/// Decodes a single frame at `timestamp_secs`, scales it to `width x height`,
+/// and returns bytes encoded in `format`.
+pub fn decode_frame(
+    path: &str,
+    timestamp_secs: f64,
+    width: u32,
+    height: u32,
+    format: ThumbnailFormat,
+    backends: &Backends,
+) -> Result<Vec<u8>, MediaError> {
+    #[cfg(feature = "libav")]
+    {
+        let _ = backends;
+        libav::decode_frame(path, timestamp_secs, width, height, format)
+    }
+    #[cfg(not(feature = "libav"))]
+    {
+        subprocess::decode_frame(path, timestamp_secs, width, height, format, backends)
+    }
+}
+
+/// Extracts `count` evenly spaced frames from `path`, each scaled to
+/// `width x height`, returned in timeline order as bytes encoded in `format`.
+pub fn generate_timeline_frames(
+    path: &str,
+    count: u32,
+    width: u32,
+    height: u32,
+    format: ThumbnailFormat,
+    backends: &Backends,
+) -> Result<Vec<Vec<u8>>, MediaError> {
+    #[cfg(feature = "libav")]
+    {
+        let _ = backends;
+        libav::generate_timeline_frames(path, count, width, height, format)
+    }
+    #[cfg(not(feature = "libav"))]
+    {
+        subprocess::generate_timeline_frames(path, count, width, height, format, backends)
+    }
+}
+
+/// Extracts a short, muted, downscaled looping clip starting at
+/// `start_secs` and running for `duration_secs`, encoded in `format`.
+///
+/// Unlike the frame-decode paths above, this always transcodes via the
+/// `ffmpeg` binary: it's a full video encode (not a single-frame decode),
+/// which is out of scope for the `libav` feature's swscale-only pipeline.
+pub fn generate_preview_clip(
+    path: &str,
+    start_secs: f64,
+    duration_secs: f64,
+    width: u32,
+    height: u32,
+    format: PreviewFormat,
+    backends: &Backends,
+) -> Result<Vec<u8>, MediaError> {
+    subprocess::generate_preview_clip(path, start_secs, duration_secs, width, height, format, backends)
+}
@@ -0,0 +1,210 @@
+// src-tauri/src/media/subprocess.rs
+//!
+//! Default media backend: shells out to the system `ffmpeg`/`ffprobe`
+//! binaries. Used whenever the crate is built without the `libav` feature.
+
+use std::fs;
+use std::process::Command;
+
+use super::{Backends, MediaError, PreviewFormat, ThumbnailFormat};
+
+fn temp_path(backends: &Backends, prefix: &str, ext: &str) -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    backends
+        .temp_dir
+        .join(format!("{}_{}.{}", prefix, nanos, ext))
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn get_duration(path: &str, backends: &Backends) -> Result<f64, MediaError> {
+    let output = Command::new(&backends.ffprobe_path)
+        .args([
+            "-v", "error",
+            "-show_entries", "format=duration",
+            "-of", "default=noprint_wrappers=1:nokey=1",
+            path,
+        ])
+        .output()
+        .map_err(|e| MediaError::Spawn(format!("ffprobe: {}", e)))?;
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|e| MediaError::Parse(format!("duration: {}", e)))
+}
+
+pub fn decode_frame(
+    path: &str,
+    timestamp_secs: f64,
+    width: u32,
+    height: u32,
+    format: ThumbnailFormat,
+    backends: &Backends,
+) -> Result<Vec<u8>, MediaError> {
+    let out_path = temp_path(backends, "clipjourney_frame", format.extension());
+
+    let output = Command::new(&backends.ffmpeg_path)
+        .args([
+            "-ss", &format!("{:.3}", timestamp_secs),
+            "-i", path,
+            "-vframes", "1",
+            "-vf", &format!("scale={}:{}", width, height),
+            "-c:v", format.codec(),
+            "-y",
+            &out_path,
+        ])
+        .output()
+        .map_err(|e| MediaError::Spawn(format!("ffmpeg: {}", e)))?;
+
+    if !output.status.success() {
+        let _ = fs::remove_file(&out_path);
+        return Err(MediaError::Decode(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+
+    let data = fs::read(&out_path).map_err(|e| MediaError::Io(e.to_string()));
+    let _ = fs::remove_file(&out_path);
+    data
+}
+
+/// Extracts `count` evenly spaced frames from `path` in a single ffmpeg pass
+/// (one decode of the stream, via the `fps` filter) rather than spawning one
+/// process per frame.
+pub fn generate_timeline_frames(
+    path: &str,
+    count: u32,
+    width: u32,
+    height: u32,
+    format: ThumbnailFormat,
+    backends: &Backends,
+) -> Result<Vec<Vec<u8>>, MediaError> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    let duration = get_duration(path, backends)?;
+    if duration <= 0.0 {
+        return Err(MediaError::Decode(
+            "video reports zero or negative duration".into(),
+        ));
+    }
+    let fps = count as f64 / duration;
+
+    let temp_dir = &backends.temp_dir;
+    let batch_id = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let ext = format.extension();
+    let pattern = temp_dir.join(format!("clipjourney_timeline_{}_%04d.{}", batch_id, ext));
+    let pattern_str = pattern
+        .to_str()
+        .ok_or_else(|| MediaError::Io("invalid temp path".into()))?;
+
+    let output = Command::new(&backends.ffmpeg_path)
+        .args([
+            "-i", path,
+            "-vf", &format!("fps={:.6},scale={}:{}", fps, width, height),
+            "-vsync", "vfr",
+            "-c:v", format.codec(),
+            "-y",
+            pattern_str,
+        ])
+        .output()
+        .map_err(|e| MediaError::Spawn(format!("ffmpeg: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(MediaError::Decode(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+
+    let mut frames = Vec::with_capacity(count as usize);
+    for i in 1..=count {
+        let frame_path = temp_dir.join(format!("clipjourney_timeline_{}_{:04}.{}", batch_id, i, ext));
+        if let Ok(data) = fs::read(&frame_path) {
+            if !data.is_empty() {
+                frames.push(data);
+            }
+        }
+        let _ = fs::remove_file(&frame_path);
+    }
+
+    // The fps filter's own rounding can emit more than `count` frames;
+    // anything beyond what we just read above is still sitting in the temp
+    // dir and would otherwise leak there forever. Sweep the whole batch
+    // prefix rather than assuming the files stop at `count`.
+    let batch_prefix = format!("clipjourney_timeline_{}_", batch_id);
+    if let Ok(entries) = fs::read_dir(temp_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            if name.to_string_lossy().starts_with(&batch_prefix) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    // ffmpeg's fps filter can emit one fewer/more frame than requested due to
+    // rounding; truncate or pad with the last good frame so callers get
+    // exactly `count` entries. If ffmpeg wrote no frame at all, the result
+    // is empty.
+    frames.truncate(count as usize);
+    if let Some(last) = frames.last().cloned() {
+        while frames.len() < count as usize {
+            frames.push(last.clone());
+        }
+    }
+
+    Ok(frames)
+}
+
+/// Extracts a short, muted, downscaled looping clip starting at `start_secs`
+/// and running for `duration_secs`, encoded in `format`.
+pub fn generate_preview_clip(
+    path: &str,
+    start_secs: f64,
+    duration_secs: f64,
+    width: u32,
+    height: u32,
+    format: PreviewFormat,
+    backends: &Backends,
+) -> Result<Vec<u8>, MediaError> {
+    let out_path = temp_path(backends, "clipjourney_preview", format.extension());
+
+    let scale = format!("scale={}:{}", width, height);
+    let start_arg = format!("{:.3}", start_secs);
+    let duration_arg = format!("{:.3}", duration_secs);
+
+    let mut command = Command::new(&backends.ffmpeg_path);
+    command.args(["-ss", &start_arg, "-t", &duration_arg, "-i", path, "-vf", &scale, "-an"]);
+
+    match format {
+        PreviewFormat::WebP => {
+            command.args(["-loop", "0"]);
+        }
+        PreviewFormat::Mp4 => {
+            command.args(["-c:v", "libx264", "-movflags", "+faststart"]);
+        }
+    }
+
+    let output = command
+        .args(["-y", &out_path])
+        .output()
+        .map_err(|e| MediaError::Spawn(format!("ffmpeg: {}", e)))?;
+
+    if !output.status.success() {
+        let _ = fs::remove_file(&out_path);
+        return Err(MediaError::Decode(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+
+    let data = fs::read(&out_path).map_err(|e| MediaError::Io(e.to_string()));
+    let _ = fs::remove_file(&out_path);
+    data
+}
@@ -0,0 +1,151 @@
+// src-tauri/src/protocol.rs
+//!
+//! Custom `clipjourney://` URI scheme that serves generated thumbnails and
+//! frames directly to the webview, with correct `Content-Type` and HTTP
+//! range support -- avoiding the ~33% size inflation and JS-bridge copy of
+//! base64 data URLs.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use tauri::http::{Request, Response, StatusCode};
+use tauri::Manager;
+
+struct Asset {
+    bytes: Vec<u8>,
+    mime_type: &'static str,
+}
+
+/// Maximum number of assets kept alive at once. Timeline scrubbing can
+/// insert on the order of a hundred frames per hover, so without a cap the
+/// store would grow for the lifetime of the process; the oldest entry is
+/// evicted once this is exceeded.
+const MAX_ASSETS: usize = 512;
+
+#[derive(Default)]
+struct AssetStoreInner {
+    assets: HashMap<String, Asset>,
+    order: VecDeque<String>,
+}
+
+/// Registry of assets handed out as opaque `clipjourney://asset/<id>` URLs by
+/// the thumbnail commands and streamed back out by the protocol handler.
+/// Bounded to [`MAX_ASSETS`] entries, evicted oldest-first.
+#[derive(Default)]
+pub struct AssetStore {
+    inner: Mutex<AssetStoreInner>,
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+impl AssetStore {
+    /// Stores `bytes` and returns a `clipjourney://asset/<id>` URL the
+    /// webview can fetch it from. Evicts the oldest asset if the store is
+    /// at capacity.
+    pub fn insert(&self, bytes: Vec<u8>, mime_type: &'static str) -> String {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed).to_string();
+
+        let mut inner = self.inner.lock().unwrap();
+        inner.assets.insert(id.clone(), Asset { bytes, mime_type });
+        inner.order.push_back(id.clone());
+        while inner.order.len() > MAX_ASSETS {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.assets.remove(&oldest);
+            }
+        }
+
+        format!("clipjourney://asset/{}", id)
+    }
+
+    fn get(&self, id: &str) -> Option<(Vec<u8>, &'static str)> {
+        self.inner
+            .lock()
+            .unwrap()
+            .assets
+            .get(id)
+            .map(|asset| (asset.bytes.clone(), asset.mime_type))
+    }
+}
+
+/// Registers the `clipjourney://` scheme on `builder`, serving bytes out of
+/// the app's [`AssetStore`] with range-request support.
+pub fn register<R: tauri::Runtime>(builder: tauri::Builder<R>) -> tauri::Builder<R> {
+    builder.register_uri_scheme_protocol("clipjourney", |app, request| {
+        handle_request(app, request)
+    })
+}
+
+fn handle_request<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    request: Request<Vec<u8>>,
+) -> Response<Vec<u8>> {
+    let id = request
+        .uri()
+        .path()
+        .trim_start_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or("")
+        .to_string();
+
+    let store = app.state::<AssetStore>();
+    let Some((bytes, mime_type)) = store.get(&id) else {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Vec::new())
+            .unwrap();
+    };
+
+    let total_len = bytes.len() as u64;
+    if let Some((start, end)) = request
+        .headers()
+        .get("range")
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_range)
+    {
+        let end = end.min(total_len.saturating_sub(1));
+        if total_len == 0 || start >= total_len || start > end {
+            return Response::builder()
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header("Content-Range", format!("bytes */{}", total_len))
+                .body(Vec::new())
+                .unwrap();
+        }
+        let chunk = bytes[start as usize..=end as usize].to_vec();
+        return Response::builder()
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header("Content-Type", mime_type)
+            .header("Accept-Ranges", "bytes")
+            .header(
+                "Content-Range",
+                format!("bytes {}-{}/{}", start, end, total_len),
+            )
+            .header("Content-Length", chunk.len().to_string())
+            .body(chunk)
+            .unwrap();
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", mime_type)
+        .header("Accept-Ranges", "bytes")
+        .header("Content-Length", total_len.to_string())
+        .body(bytes)
+        .unwrap()
+}
+
+/// Parses a `Range: bytes=<start>-<end>` header into an inclusive
+/// `(start, end)` pair. Only the single-range form is supported, which is
+/// all major webviews send.
+fn parse_range(value: &str) -> Option<(u64, u64)> {
+    let spec = value.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+    let start: u64 = start.parse().ok()?;
+    let end: u64 = if end.is_empty() {
+        u64::MAX
+    } else {
+        end.parse().ok()?
+    };
+    Some((start, end))
+}